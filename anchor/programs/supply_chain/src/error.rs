@@ -12,7 +12,7 @@ pub enum SupplyChainError {
     CounterOverflow,
     #[msg("Invalid stage name: must be 1-50 characters")]
     InvalidStageName,
-    #[msg("Too many stages: maximum 10 stages allowed")]
+    #[msg("Too many stages: maximum 64 stages allowed")]
     TooManyStages,
     #[msg("No stages defined")]
     NoStages,
@@ -20,4 +20,10 @@ pub enum SupplyChainError {
     InvalidStageIndex,
     #[msg("Current stage not completed")]
     StageNotCompleted,
+    #[msg("Product has already been delivered")]
+    ProductAlreadyDelivered,
+    #[msg("No stages supplied to append")]
+    NoStagesToAppend,
+    #[msg("Event account does not match the expected PDA for this product and event index")]
+    InvalidEventAccount,
 }
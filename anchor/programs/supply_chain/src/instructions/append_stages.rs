@@ -0,0 +1,58 @@
+#![allow(clippy::result_large_err)]
+
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::SupplyChainError;
+
+#[derive(Accounts)]
+pub struct AppendStages<'info> {
+    #[account(mut)]
+    pub product_account: AccountLoader<'info, Product>,
+
+    #[account(mut, constraint = owner.key() == product_account.load()?.owner @ SupplyChainError::UnauthorizedAccess)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Fills previously-unused stage slots in `product_account.stages`.
+///
+/// `Product` already reserves all `MAX_STAGES` slots (and the rent for
+/// them) at `initialize_product` time, so this never resizes the account -
+/// it just lets a product whose full route wasn't known up front add
+/// stages later, up to the fixed 64-stage capacity. New stages go after
+/// the existing ones; a call that would take the total past `MAX_STAGES`
+/// fails with `TooManyStages`, and no lamports are moved.
+pub fn process_append_stages(ctx: Context<AppendStages>, stages: Vec<StageInput>) -> Result<()> {
+    require!(!stages.is_empty(), SupplyChainError::NoStagesToAppend);
+
+    let mut product_account = ctx.accounts.product_account.load_mut()?;
+
+    require!(
+        ProductStatus::try_from_u8(product_account.status) != Some(ProductStatus::Delivered),
+        SupplyChainError::ProductAlreadyDelivered
+    );
+
+    let current_count = product_account.stage_count as usize;
+
+    require!(
+        current_count + stages.len() <= Product::MAX_STAGES,
+        SupplyChainError::TooManyStages
+    );
+
+    for (offset, stage) in stages.iter().enumerate() {
+        require!(
+            stage.name.len() <= Product::STAGE_NAME_MAX_LEN && !stage.name.is_empty(),
+            SupplyChainError::InvalidStageName
+        );
+
+        let index = current_count + offset;
+        product_account.stages[index].set_name(&stage.name);
+        product_account.stages[index].set_owner(stage.owner);
+        product_account.stages[index].completed = 0;
+    }
+
+    product_account.stage_count = (current_count + stages.len()) as u8;
+
+    Ok(())
+}
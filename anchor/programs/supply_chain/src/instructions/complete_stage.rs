@@ -5,7 +5,7 @@ use crate::error::*;
 #[derive(Accounts)]
 pub struct CompleteStage<'info> {
     #[account(mut)]
-    pub product_account: Account<'info, Product>,
+    pub product_account: AccountLoader<'info, Product>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -14,7 +14,8 @@ pub struct CompleteStage<'info> {
 }
 
 pub fn process_complete_stage(ctx: Context<CompleteStage>) -> Result<()> {
-    let product_account = &mut ctx.accounts.product_account;
+    let product_key = ctx.accounts.product_account.key();
+    let mut product_account = ctx.accounts.product_account.load_mut()?;
 
     require_eq!(
         ctx.accounts.signer.key(),
@@ -25,29 +26,50 @@ pub fn process_complete_stage(ctx: Context<CompleteStage>) -> Result<()> {
     let current_stage_index = product_account.current_stage_index as usize;
 
     require!(
-        current_stage_index < product_account.stages.len(),
+        current_stage_index < product_account.stage_count as usize,
         SupplyChainError::InvalidStageIndex
     );
 
     // Mark current stage as completed
-    product_account.stages[current_stage_index].completed = true;
+    product_account.stages[current_stage_index].completed = 1;
+    let stage_name = product_account.stages[current_stage_index].name().to_string();
+
+    let mut new_owner = None;
 
     // Check if there's a next stage
-    if current_stage_index + 1 < product_account.stages.len() {
+    if current_stage_index + 1 < product_account.stage_count as usize {
         let next_stage_index = current_stage_index + 1;
 
         // If next stage has a wallet, transfer ownership
-        if let Some(next_owner) = product_account.stages[next_stage_index].owner {
+        if let Some(next_owner) = product_account.stages[next_stage_index].owner() {
             product_account.owner = next_owner;
-            product_account.status = ProductStatus::Transferred;
+            product_account.status = ProductStatus::Transferred.into();
+            new_owner = Some(next_owner);
         }
 
         // Move to next stage
         product_account.current_stage_index = next_stage_index as u8;
     } else {
         // All stages completed
-        product_account.status = ProductStatus::Delivered;
+        product_account.status = ProductStatus::Delivered.into();
     }
 
+    let event_index = product_account.events_counter;
+    product_account.events_counter = product_account
+        .events_counter
+        .checked_add(1)
+        .ok_or(SupplyChainError::CounterOverflow)?;
+
+    emit!(ProductEventLogged {
+        product: product_key,
+        event_index,
+        event_type: EventType::Complete,
+        stage_name,
+        description: "Stage completed".to_string(),
+        timestamp: Clock::get()?.unix_timestamp,
+        new_owner,
+        new_status: product_account.status,
+    });
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -14,7 +14,7 @@ pub struct InitializeProduct<'info> {
         seeds = [b"product", owner.key().as_ref(), serial_number.as_bytes()],
         bump
     )]
-    pub product_account: Account<'info, Product>,
+    pub product_account: AccountLoader<'info, Product>,
 
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -28,48 +28,51 @@ pub fn process_initialize_product(
     ctx: Context<InitializeProduct>,
     serial_number: String,
     description: String,
-    stages: Option<Vec<Stage>>,
+    stages: Option<Vec<StageInput>>,
 ) -> Result<()> {
-    let product_account = &mut ctx.accounts.product_account;
     let clock = Clock::get()?;
 
     require!(
-        serial_number.len() <= 50 && !serial_number.is_empty(),
+        serial_number.len() <= SERIAL_NUMBER_LEN && !serial_number.is_empty(),
         SupplyChainError::InvalidSerialNumber
     );
 
     require!(
-        description.len() <= 200 && !description.is_empty(),
+        description.len() <= DESCRIPTION_LEN && !description.is_empty(),
         SupplyChainError::InvalidDescription
     );
 
+    let mut product_account = ctx.accounts.product_account.load_init()?;
+
+    product_account.owner = ctx.accounts.owner.key();
+    product_account.set_serial_number(&serial_number);
+    product_account.set_description(&description);
+    product_account.status = ProductStatus::Created.into();
+    product_account.created_at = clock.unix_timestamp;
+    product_account.events_counter = 0;
+    product_account.current_stage_index = 0;
+
     if let Some(stages) = stages {
         require!(
             stages.len() <= Product::MAX_STAGES,
             SupplyChainError::TooManyStages
         );
 
-        for stage in &stages {
+        for (index, stage) in stages.iter().enumerate() {
             require!(
                 stage.name.len() <= Product::STAGE_NAME_MAX_LEN && !stage.name.is_empty(),
                 SupplyChainError::InvalidStageName
             );
+
+            product_account.stages[index].set_name(&stage.name);
+            product_account.stages[index].set_owner(stage.owner);
+            product_account.stages[index].completed = 0;
         }
 
-        product_account.stages = stages;
-        product_account.use_stages = true;
+        product_account.stage_count = stages.len() as u8;
     } else {
-        product_account.stages = Vec::new();
-        product_account.use_stages = false;
+        product_account.stage_count = 0;
     }
 
-    product_account.current_stage_index = 0;
-    product_account.owner = ctx.accounts.owner.key();
-    product_account.serial_number = serial_number;
-    product_account.description = description;
-    product_account.status = ProductStatus::Created;
-    product_account.created_at = clock.unix_timestamp;
-    product_account.events_counter = 0;
-
     Ok(())
 }
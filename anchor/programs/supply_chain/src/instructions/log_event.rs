@@ -2,21 +2,21 @@
 
 use crate::state::*;
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
 use crate::error::*;
 
 #[derive(Accounts)]
 pub struct LogEvent<'info> {
     #[account(mut)]
-    pub product_account: Account<'info, Product>,
+    pub product_account: AccountLoader<'info, Product>,
 
-    #[account(
-        init,
-        payer = signer,
-        space = SupplyChainEvent::LEN,
-        seeds = [b"event", product_account.key().as_ref(), product_account.events_counter.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub event_account: Account<'info, SupplyChainEvent>,
+    /// CHECK: must be the PDA derived from `product_account` and the
+    /// product's current `events_counter`; only written to (and only
+    /// needs to be rent-funded) when the caller asks for `record_history`.
+    /// Clients that only care about the `ProductEventLogged` log can pass
+    /// this PDA without ever funding it.
+    #[account(mut)]
+    pub event_account: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub signer: Signer<'info>,
@@ -28,43 +28,58 @@ pub struct LogEvent<'info> {
  * Processes the log_event instruction.
  * This function logs an event for a product, updating its status and stages as necessary.
  * Allows logging multiple events per stage, with the ability to mark stages as completed.
+ *
+ * Every call emits a `ProductEventLogged` event so clients can reconstruct
+ * history off-chain from logs alone. When `record_history` is true, the
+ * event is additionally persisted into a `SupplyChainEvent` PDA for
+ * programs/indexers that need an on-chain, queryable record; high-volume
+ * products can pass `false` to avoid paying rent for events they don't
+ * need to read back on-chain.
  */
-pub fn process_log_event(ctx: Context<LogEvent>, event_type: EventType, description: String) -> Result<()> {
-    let product_account = &mut ctx.accounts.product_account;
-    let event_account = &mut ctx.accounts.event_account;
+pub fn process_log_event(
+    ctx: Context<LogEvent>,
+    event_type: EventType,
+    description: String,
+    record_history: bool,
+) -> Result<()> {
+    let product_key = ctx.accounts.product_account.key();
+    let mut product_account = ctx.accounts.product_account.load_mut()?;
     let clock = Clock::get()?;
 
     require!(
-        description.len() <= 200 && !description.is_empty(),
+        description.len() <= SupplyChainEvent::MAX_DESCRIPTION_LEN && !description.is_empty(),
         SupplyChainError::InvalidDescription
     );
     require!(
-        product_account.status != ProductStatus::Delivered,
+        ProductStatus::try_from_u8(product_account.status) != Some(ProductStatus::Delivered),
         SupplyChainError::ProductAlreadyDelivered
     );
 
-    product_account.status = ProductStatus::InTransit;
+    product_account.status = ProductStatus::InTransit.into();
+
+    let mut stage_name = String::new();
+    let mut new_owner = None;
 
     // Check if product has stages
-    if !product_account.stages.is_empty() {
+    if product_account.stage_count > 0 {
         // Product has stages - validate current stage access
         let current_stage_index = product_account.current_stage_index as usize;
-        
+
         require!(
-            current_stage_index < product_account.stages.len(),
+            current_stage_index < product_account.stage_count as usize,
             SupplyChainError::InvalidStageIndex
         );
 
         let current_stage = &product_account.stages[current_stage_index];
-        
+
         // Check if current stage is already completed
         require!(
-            !current_stage.completed,
+            !current_stage.is_completed(),
             SupplyChainError::StageNotCompleted
         );
 
         // Verify that the signer is the owner of the current stage
-        if let Some(stage_owner) = current_stage.owner {
+        if let Some(stage_owner) = current_stage.owner() {
             require_eq!(
                 ctx.accounts.signer.key(),
                 stage_owner,
@@ -73,23 +88,23 @@ pub fn process_log_event(ctx: Context<LogEvent>, event_type: EventType, descript
         }
 
         // Use the current stage name for the event
-        event_account.stage_name = current_stage.name.clone();
+        stage_name = current_stage.name().to_string();
 
         // If event type is Complete, mark the current stage as completed
         if event_type == EventType::Complete {
-            product_account.stages[current_stage_index].completed = true;
-            
+            product_account.stages[current_stage_index].completed = 1;
+
             // Move to next stage if not the last stage
-            if current_stage_index + 1 < product_account.stages.len() {
+            if current_stage_index + 1 < product_account.stage_count as usize {
                 product_account.current_stage_index += 1;
 
                 // If next stage has a wallet, transfer ownership
-                if let Some(next_owner) = product_account.stages[current_stage_index + 1].owner {
+                if let Some(next_owner) = product_account.stages[current_stage_index + 1].owner() {
                     product_account.owner = next_owner;
-                    product_account.status = ProductStatus::Transferred;
+                    product_account.status = ProductStatus::Transferred.into();
+                    new_owner = Some(next_owner);
                 }
             }
-
         }
     } else {
         // Product has no stages - only the product owner can create events
@@ -100,16 +115,108 @@ pub fn process_log_event(ctx: Context<LogEvent>, event_type: EventType, descript
         );
     }
 
-    // Populate event account
-    event_account.product = product_account.key();
-    event_account.event_type = event_type.clone();
-    event_account.description = description;
-    event_account.timestamp = clock.unix_timestamp;
-    event_account.event_index = product_account.events_counter;
+    let event_index = product_account.events_counter;
+    let new_status = product_account.status;
 
     product_account.events_counter = product_account
         .events_counter
         .checked_add(1)
         .ok_or(SupplyChainError::CounterOverflow)?;
+
+    drop(product_account);
+
+    require!(
+        stage_name.len() <= SupplyChainEvent::MAX_STAGE_NAME_LEN,
+        SupplyChainError::InvalidStageName
+    );
+
+    if record_history {
+        create_event_account(
+            &ctx.accounts.event_account,
+            &ctx.accounts.signer,
+            &ctx.accounts.system_program,
+            &product_key,
+            event_index,
+            &event_type,
+            &description,
+            &stage_name,
+            clock.unix_timestamp,
+        )?;
+    }
+
+    emit!(ProductEventLogged {
+        product: product_key,
+        event_index,
+        event_type,
+        stage_name,
+        description,
+        timestamp: clock.unix_timestamp,
+        new_owner,
+        new_status,
+    });
+
     Ok(())
 }
+
+#[allow(clippy::too_many_arguments)]
+fn create_event_account<'info>(
+    event_account: &UncheckedAccount<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    product_key: &Pubkey,
+    event_index: u64,
+    event_type: &EventType,
+    description: &str,
+    stage_name: &str,
+    timestamp: i64,
+) -> Result<()> {
+    let (expected_key, bump) = Pubkey::find_program_address(
+        &[
+            b"event",
+            product_key.as_ref(),
+            event_index.to_le_bytes().as_ref(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        event_account.key(),
+        expected_key,
+        SupplyChainError::InvalidEventAccount
+    );
+
+    let rent = Rent::get()?;
+    let space = SupplyChainEvent::space_for(description.len(), stage_name.len());
+    let seeds: &[&[u8]] = &[
+        b"event",
+        product_key.as_ref(),
+        event_index.to_le_bytes().as_ref(),
+        &[bump],
+    ];
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: event_account.to_account_info(),
+            },
+            &[seeds],
+        ),
+        rent.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )?;
+
+    let event_data = SupplyChainEvent {
+        product: *product_key,
+        event_type: event_type.clone(),
+        description: description.to_string(),
+        stage_name: stage_name.to_string(),
+        timestamp,
+        event_index,
+    };
+
+    let mut account_data = event_account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut account_data;
+    event_data.try_serialize(&mut writer)
+}
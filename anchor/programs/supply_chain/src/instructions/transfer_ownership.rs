@@ -5,7 +5,8 @@ use crate::state::*;
 use crate::error::*;
 
 pub fn process_transfer_ownership(ctx: Context<TransferOwnership>, new_owner: Pubkey) -> Result<()> {
-    let product_account = &mut ctx.accounts.product_account;
+    let product_key = ctx.accounts.product_account.key();
+    let mut product_account = ctx.accounts.product_account.load_mut()?;
 
     require_eq!(
         ctx.accounts.current_owner.key(),
@@ -14,14 +15,39 @@ pub fn process_transfer_ownership(ctx: Context<TransferOwnership>, new_owner: Pu
     );
 
     product_account.owner = new_owner;
-    product_account.status = ProductStatus::Transferred;
+    product_account.status = ProductStatus::Transferred.into();
+
+    let event_index = product_account.events_counter;
+    product_account.events_counter = product_account
+        .events_counter
+        .checked_add(1)
+        .ok_or(SupplyChainError::CounterOverflow)?;
+
+    let stage_name = if product_account.stage_count > 0 {
+        let current_stage_index = product_account.current_stage_index as usize;
+        product_account.stages[current_stage_index].name().to_string()
+    } else {
+        String::new()
+    };
+
+    emit!(ProductEventLogged {
+        product: product_key,
+        event_index,
+        event_type: EventType::Ongoing,
+        stage_name,
+        description: "Ownership transferred".to_string(),
+        timestamp: Clock::get()?.unix_timestamp,
+        new_owner: Some(new_owner),
+        new_status: product_account.status,
+    });
+
     Ok(())
 }
 
 #[derive(Accounts)]
 pub struct TransferOwnership<'info> {
     #[account(mut)]
-    pub product_account: Account<'info, Product>,
+    pub product_account: AccountLoader<'info, Product>,
 
     #[account(mut)]
     pub current_owner: Signer<'info>,
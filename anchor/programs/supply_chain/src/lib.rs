@@ -19,7 +19,7 @@ pub mod supply_chain_program {
         ctx: Context<InitializeProduct>,
         serial_number: String,
         description: String,
-        stages: Option<Vec<Stage>>,
+        stages: Option<Vec<StageInput>>,
     ) -> Result<()> {
         process_initialize_product(ctx, serial_number, description, stages)
     }
@@ -27,12 +27,17 @@ pub mod supply_chain_program {
     pub fn log_event(
         ctx: Context<LogEvent>,
         event_type: EventType,
-        description: String
+        description: String,
+        record_history: bool,
     ) -> Result<()> {
-        process_log_event(ctx, event_type, description)
+        process_log_event(ctx, event_type, description, record_history)
     }
 
     pub fn transfer_ownership(ctx: Context<TransferOwnership>, new_owner: Pubkey) -> Result<()> {
         process_transfer_ownership(ctx, new_owner)
     }
+
+    pub fn append_stages(ctx: Context<AppendStages>, stages: Vec<StageInput>) -> Result<()> {
+        process_append_stages(ctx, stages)
+    }
 }
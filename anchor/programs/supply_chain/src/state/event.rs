@@ -14,20 +14,28 @@ impl SupplyChainEvent {
     const DISCRIMINATOR_LEN: usize = 8;
     const PUBKEY_LEN: usize = 32;
     const STRING_LEN_PREFIX: usize = 4;
-    const MAX_DESCRIPTION_LEN: usize = 200;
-    const MAX_STAGE_NAME_LEN: usize = 50;
+    // Validation bounds only - accounts are now sized to the measured
+    // length of `description`/`stage_name`, not these maximums. See
+    // `space_for`.
+    pub const MAX_DESCRIPTION_LEN: usize = 200;
+    pub const MAX_STAGE_NAME_LEN: usize = 50;
     const I64_LEN: usize = 8;
     const U64_LEN: usize = 8;
 
-    pub const LEN: usize = Self::DISCRIMINATOR_LEN
-        + Self::PUBKEY_LEN
-        + EventType::LEN
-        + Self::STRING_LEN_PREFIX
-        + Self::MAX_DESCRIPTION_LEN
-        + Self::STRING_LEN_PREFIX
-        + Self::MAX_STAGE_NAME_LEN
-        + Self::I64_LEN
-        + Self::U64_LEN;
+    /// Exact account size for a description of `description_len` bytes and
+    /// a stage name of `stage_name_len` bytes. Lengths must already be
+    /// validated against `MAX_DESCRIPTION_LEN`/`MAX_STAGE_NAME_LEN`.
+    pub fn space_for(description_len: usize, stage_name_len: usize) -> usize {
+        Self::DISCRIMINATOR_LEN
+            + Self::PUBKEY_LEN
+            + EventType::LEN
+            + Self::STRING_LEN_PREFIX
+            + description_len
+            + Self::STRING_LEN_PREFIX
+            + stage_name_len
+            + Self::I64_LEN
+            + Self::U64_LEN
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -38,4 +46,22 @@ pub enum EventType {
 
 impl EventType {
     pub const LEN: usize = 1;
+}
+
+/// Structured log of a single supply-chain event, emitted via `emit!` from
+/// `log_event`, `transfer_ownership` and `complete_stage`. Indexers can follow
+/// `event_index` (the product's monotonic `events_counter`) to detect gaps,
+/// and reconstruct a product's event history from these logs alone - no
+/// `SupplyChainEvent` PDA is required to read it back. `initialize_product`
+/// and `append_stages` emit no event.
+#[event]
+pub struct ProductEventLogged {
+    pub product: Pubkey,
+    pub event_index: u64,
+    pub event_type: EventType,
+    pub stage_name: String,
+    pub description: String,
+    pub timestamp: i64,
+    pub new_owner: Option<Pubkey>,
+    pub new_status: u8,
 }
\ No newline at end of file
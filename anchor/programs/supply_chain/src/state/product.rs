@@ -2,55 +2,125 @@
 
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+/// Maximum number of stages a product's supply chain can describe.
+///
+/// Stages live inline in a fixed-size array inside the zero-copy `Product`
+/// account, so raising this bumps the account's on-chain size for every
+/// product regardless of how many stages it actually uses.
+pub const MAX_STAGES: usize = 64;
+pub const STAGE_NAME_LEN: usize = 50;
+pub const SERIAL_NUMBER_LEN: usize = 50;
+pub const DESCRIPTION_LEN: usize = 200;
+
+/// A single stage of a product's route, stored inline in the `Product`
+/// account.
+///
+/// Every field is fixed-size so the struct is `Pod`/`Zeroable` and can live
+/// inside a `[Stage; MAX_STAGES]` array without any (de)serialization.
+/// Names and the optional owner are packed into plain byte buffers with an
+/// explicit length/flag rather than `String`/`Option<Pubkey>`.
+#[zero_copy]
+#[derive(Debug)]
 pub struct Stage {
+    pub name: [u8; STAGE_NAME_LEN],
+    pub name_len: u8,
+    pub owner: Pubkey,
+    pub has_owner: u8,
+    pub completed: u8,
+}
+
+impl Stage {
+    pub fn name(&self) -> &str {
+        std::str::from_utf8(&self.name[..self.name_len as usize]).unwrap_or_default()
+    }
+
+    pub fn owner(&self) -> Option<Pubkey> {
+        if self.has_owner == 1 {
+            Some(self.owner)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed == 1
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = [0u8; STAGE_NAME_LEN];
+        self.name[..name.len()].copy_from_slice(name.as_bytes());
+        self.name_len = name.len() as u8;
+    }
+
+    pub fn set_owner(&mut self, owner: Option<Pubkey>) {
+        match owner {
+            Some(owner) => {
+                self.owner = owner;
+                self.has_owner = 1;
+            }
+            None => {
+                self.owner = Pubkey::default();
+                self.has_owner = 0;
+            }
+        }
+    }
+}
+
+/// Instruction-side description of a stage, supplied by clients when a
+/// product is created (or, via `append_stages`, added to later). This is
+/// the Borsh-friendly counterpart to the zero-copy [`Stage`] stored on
+/// chain; processors translate one into the other.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StageInput {
     pub name: String,
     pub owner: Option<Pubkey>,
-    pub completed: bool,
 }
 
-#[account]
+#[account(zero_copy)]
 pub struct Product {
     pub owner: Pubkey,
-    pub serial_number: String,
-    pub description: String,
-    pub status: ProductStatus,
+    pub serial_number: [u8; SERIAL_NUMBER_LEN],
+    pub serial_number_len: u8,
+    pub description: [u8; DESCRIPTION_LEN],
+    pub description_len: u8,
+    pub status: u8,
     pub created_at: i64,
     pub events_counter: u64,
-    pub stages: Vec<Stage>,
+    pub stages: [Stage; MAX_STAGES],
+    pub stage_count: u8,
     pub current_stage_index: u8,
 }
 
 impl Product {
-    const DISCRIMINATOR_LEN: usize = 8;
-    const PUBKEY_LEN: usize = 32;
-    const STRING_LEN_PREFIX: usize = 4;
-    const MAX_SERIAL_NUMBER_LEN: usize = 50;
-    const MAX_DESCRIPTION_LEN: usize = 200;
-    const I64_LEN: usize = 8;
-    const U64_LEN: usize = 8;
-    const BOOL_LEN: usize = 1;
-    const OPTION_LEN: usize = 1;
-    const U8_LEN: usize = 1;
-    const VEC_LEN_PREFIX: usize = 4;
-    pub const MAX_STAGES: usize = 10; // Maximum number of stages allowed Might need to adjust based on requirements
-    pub const STAGE_NAME_MAX_LEN: usize = 50;
-
-    pub const LEN: usize = Self::DISCRIMINATOR_LEN
-        + Self::PUBKEY_LEN
-        + Self::STRING_LEN_PREFIX
-        + Self::MAX_SERIAL_NUMBER_LEN
-        + Self::STRING_LEN_PREFIX
-        + Self::MAX_DESCRIPTION_LEN
-        + ProductStatus::LEN
-        + Self::I64_LEN
-        + Self::U64_LEN 
-        + Self::VEC_LEN_PREFIX
-        + (Self::MAX_STAGES * (Self::STRING_LEN_PREFIX + Self::STAGE_NAME_MAX_LEN + Self::OPTION_LEN + Self::PUBKEY_LEN + Self::BOOL_LEN))
-        + Self::U8_LEN;
+    pub const MAX_STAGES: usize = MAX_STAGES;
+    pub const STAGE_NAME_MAX_LEN: usize = STAGE_NAME_LEN;
+
+    pub const LEN: usize = 8 + std::mem::size_of::<Self>();
+
+    pub fn serial_number(&self) -> &str {
+        std::str::from_utf8(&self.serial_number[..self.serial_number_len as usize])
+            .unwrap_or_default()
+    }
+
+    pub fn description(&self) -> &str {
+        std::str::from_utf8(&self.description[..self.description_len as usize])
+            .unwrap_or_default()
+    }
+
+    pub fn set_serial_number(&mut self, serial_number: &str) {
+        self.serial_number = [0u8; SERIAL_NUMBER_LEN];
+        self.serial_number[..serial_number.len()].copy_from_slice(serial_number.as_bytes());
+        self.serial_number_len = serial_number.len() as u8;
+    }
+
+    pub fn set_description(&mut self, description: &str) {
+        self.description = [0u8; DESCRIPTION_LEN];
+        self.description[..description.len()].copy_from_slice(description.as_bytes());
+        self.description_len = description.len() as u8;
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum ProductStatus {
     Created,
     InTransit,
@@ -60,5 +130,20 @@ pub enum ProductStatus {
 }
 
 impl ProductStatus {
-    pub const LEN: usize = 1;
+    pub fn try_from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Created),
+            1 => Some(Self::InTransit),
+            2 => Some(Self::Received),
+            3 => Some(Self::Delivered),
+            4 => Some(Self::Transferred),
+            _ => None,
+        }
+    }
+}
+
+impl From<ProductStatus> for u8 {
+    fn from(status: ProductStatus) -> Self {
+        status as u8
+    }
 }